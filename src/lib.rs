@@ -5,6 +5,16 @@ use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
+mod mime;
+mod paginate;
+mod query;
+mod template;
+mod watch;
+
+pub use mime::{Attachment, MimePart};
+pub use query::MessageQuery;
+pub use template::MessageTemplate;
+
 const APPLICATION_JSON: &str = "application/json";
 
 #[derive(Debug, Clone)]
@@ -37,6 +47,22 @@ pub struct SearchParams {
     limit: Option<i64>,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ReleaseParams {
+    #[serde(rename = "Host")]
+    host: String,
+    #[serde(rename = "Port")]
+    port: String,
+    #[serde(rename = "Email")]
+    email: Option<String>,
+    #[serde(rename = "Mechanism")]
+    mechanism: Option<String>,
+    #[serde(rename = "Username")]
+    username: Option<String>,
+    #[serde(rename = "Password")]
+    password: Option<String>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct EmailAddr {
     #[serde(rename = "Mailbox")]
@@ -141,11 +167,51 @@ impl MailHog {
             .json()
             .await?)
     }
+
+    pub async fn delete_message(&self, id: &str) -> Result<()> {
+        self.client
+            .execute(
+                self.client
+                    .delete(format!("{}/api/v1/messages/{}", self.base_url, id))
+                    .header(ACCEPT, APPLICATION_JSON)
+                    .build()?,
+            )
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    pub async fn delete_all_messages(&self) -> Result<()> {
+        self.client
+            .execute(
+                self.client
+                    .delete(format!("{}/api/v1/messages", self.base_url))
+                    .header(ACCEPT, APPLICATION_JSON)
+                    .build()?,
+            )
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    pub async fn release_message(&self, id: &str, params: ReleaseParams) -> Result<()> {
+        self.client
+            .execute(
+                self.client
+                    .post(format!("{}/api/v1/messages/{}/release", self.base_url, id))
+                    .json(&params)
+                    .header(ACCEPT, APPLICATION_JSON)
+                    .build()?,
+            )
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{ListMessagesParams, MailHog, SearchKind, SearchParams};
+    use crate::{ListMessagesParams, MailHog, ReleaseParams, SearchKind, SearchParams};
     use chrono::Utc;
     use lettre::transport::smtp::client::Tls;
     use lettre::{Message, SmtpTransport, Transport};
@@ -411,4 +477,95 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn delete_and_release_messages() {
+        let cli = Cli::docker();
+        let env = setup(&cli);
+        let mh = env.mh;
+        let mailer = env.mailer;
+
+        let first = make_rand_message(Default::default());
+        let second = make_rand_message(Default::default());
+        for msg in [&first, &second] {
+            mailer
+                .send(
+                    &Message::builder()
+                        .from(msg.from.parse().unwrap())
+                        .to(msg.to.parse().unwrap())
+                        .subject(&msg.subject)
+                        .body(msg.body.to_string())
+                        .unwrap(),
+                )
+                .unwrap();
+        }
+
+        let message_list = mh
+            .list_messages(ListMessagesParams {
+                start: None,
+                limit: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(2, message_list.total);
+
+        const SUBJECT: &str = "Subject";
+        let first_id = message_list
+            .items
+            .iter()
+            .find(|m| m.content.headers.get(SUBJECT) == Some(&vec![first.subject.clone()]))
+            .unwrap()
+            .id
+            .clone();
+
+        mh.delete_message(&first_id).await.unwrap();
+
+        let message_list = mh
+            .list_messages(ListMessagesParams {
+                start: None,
+                limit: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(1, message_list.total);
+        assert!(message_list.items.iter().all(|m| m.id != first_id));
+
+        let remaining_id = message_list.items[0].id.clone();
+        let remaining_to = message_list.items[0].to[0].to_string();
+        // MailHog performs the release from inside its container, so it must
+        // connect to its own SMTP listener, not the host-mapped port.
+        mh.release_message(
+            &remaining_id,
+            ReleaseParams {
+                host: "localhost".to_string(),
+                port: SMTP_PORT.to_string(),
+                email: Some(remaining_to),
+                mechanism: None,
+                username: None,
+                password: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let message_list = mh
+            .list_messages(ListMessagesParams {
+                start: None,
+                limit: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(2, message_list.total);
+
+        mh.delete_all_messages().await.unwrap();
+
+        let message_list = mh
+            .list_messages(ListMessagesParams {
+                start: None,
+                limit: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(0, message_list.total);
+    }
 }
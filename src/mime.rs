@@ -0,0 +1,347 @@
+use crate::MessageContent;
+use anyhow::Result;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::collections::HashMap;
+
+/// A single node in the MIME tree produced by [`MessageContent::parse_mime`].
+///
+/// Multipart entities have no `body` of their own and carry their parts in
+/// `children`; leaf entities have a decoded `body` and no `children`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MimePart {
+    pub content_type: String,
+    pub params: HashMap<String, String>,
+    pub headers: HashMap<String, Vec<String>>,
+    pub body: Vec<u8>,
+    pub children: Vec<MimePart>,
+}
+
+/// A decoded attachment extracted from a [`MimePart`] tree.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Attachment {
+    pub filename: Option<String>,
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+impl MimePart {
+    /// The decoded `text/plain` body, if this tree contains one.
+    pub fn text_plain(&self) -> Option<String> {
+        self.find_leaf("text/plain")
+    }
+
+    /// The decoded `text/html` body, if this tree contains one.
+    pub fn text_html(&self) -> Option<String> {
+        self.find_leaf("text/html")
+    }
+
+    fn find_leaf(&self, content_type: &str) -> Option<String> {
+        if self.children.is_empty() {
+            let is_match = self.content_type == content_type && self.attachment_filename().is_none();
+            return is_match.then(|| String::from_utf8_lossy(&self.body).into_owned());
+        }
+        self.children.iter().find_map(|child| child.find_leaf(content_type))
+    }
+
+    /// Every leaf part that carries a filename, decoded to raw bytes.
+    pub fn attachments(&self) -> Vec<Attachment> {
+        let mut out = Vec::new();
+        self.collect_attachments(&mut out);
+        out
+    }
+
+    fn collect_attachments(&self, out: &mut Vec<Attachment>) {
+        if self.children.is_empty() {
+            if let Some(filename) = self.attachment_filename() {
+                out.push(Attachment {
+                    filename: Some(filename),
+                    mime_type: self.content_type.clone(),
+                    data: self.body.clone(),
+                });
+            }
+        } else {
+            for child in &self.children {
+                child.collect_attachments(out);
+            }
+        }
+    }
+
+    fn attachment_filename(&self) -> Option<String> {
+        if let Some(disposition) = header_get(&self.headers, "Content-Disposition") {
+            let (_, params) = parse_content_type(disposition);
+            if let Some(filename) = params.get("filename") {
+                return Some(filename.clone());
+            }
+        }
+        self.params.get("name").cloned()
+    }
+}
+
+impl MessageContent {
+    /// Recursively parse this message's headers and body into a [`MimePart`]
+    /// tree, splitting multipart bodies on their boundary and decoding each
+    /// leaf's `Content-Transfer-Encoding`.
+    pub fn parse_mime(&self) -> Result<MimePart> {
+        Ok(build_part(self.headers.clone(), &self.body))
+    }
+}
+
+fn build_part(headers: HashMap<String, Vec<String>>, raw_body: &str) -> MimePart {
+    let (content_type, params) = header_get(&headers, "Content-Type")
+        .map(parse_content_type)
+        .unwrap_or_else(|| ("text/plain".to_string(), HashMap::new()));
+
+    if content_type.starts_with("multipart/") {
+        if let Some(boundary) = params.get("boundary") {
+            let children = split_multipart(raw_body, boundary)
+                .into_iter()
+                .map(|segment| {
+                    let (part_headers, part_body) = split_headers_body(segment);
+                    build_part(part_headers, part_body)
+                })
+                .collect();
+            return MimePart {
+                content_type,
+                params,
+                headers,
+                body: Vec::new(),
+                children,
+            };
+        }
+    }
+
+    let transfer_encoding = header_get(&headers, "Content-Transfer-Encoding")
+        .unwrap_or("7bit")
+        .trim()
+        .to_ascii_lowercase();
+    let body = decode_body(raw_body, &transfer_encoding);
+    MimePart {
+        content_type,
+        params,
+        headers,
+        body,
+        children: Vec::new(),
+    }
+}
+
+pub(crate) fn header_get<'a>(headers: &'a HashMap<String, Vec<String>>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .and_then(|(_, v)| v.first())
+        .map(|s| s.as_str())
+}
+
+fn parse_content_type(value: &str) -> (String, HashMap<String, String>) {
+    let mut segments = value.split(';');
+    let content_type = segments.next().unwrap_or("").trim().to_ascii_lowercase();
+    let mut params = HashMap::new();
+    for segment in segments {
+        if let Some((key, value)) = segment.split_once('=') {
+            params.insert(
+                key.trim().to_ascii_lowercase(),
+                value.trim().trim_matches('"').to_string(),
+            );
+        }
+    }
+    (content_type, params)
+}
+
+fn split_multipart<'a>(body: &'a str, boundary: &str) -> Vec<&'a str> {
+    let delimiter = format!("--{}", boundary);
+    let marks = boundary_line_starts(body, &delimiter);
+
+    marks
+        .windows(2)
+        .filter_map(|pair| {
+            let (start, end) = (pair[0], pair[1]);
+            let segment = &body[start + delimiter.len()..end];
+            if segment.starts_with("--") {
+                return None;
+            }
+            let segment = segment.trim_start_matches(['\r', '\n']);
+            (!segment.trim().is_empty()).then_some(segment)
+        })
+        .collect()
+}
+
+/// Byte offsets of every occurrence of `delimiter` that starts a line (either
+/// at the very start of `body` or immediately after a `\n`), per RFC 2046's
+/// requirement that a boundary delimiter line begin with CRLF. This keeps the
+/// delimiter string from matching if it happens to recur inside a part's
+/// (e.g. base64) payload.
+fn boundary_line_starts(body: &str, delimiter: &str) -> Vec<usize> {
+    let mut marks = Vec::new();
+    let mut search_from = 0;
+    while let Some(rel) = body.get(search_from..).and_then(|rest| rest.find(delimiter)) {
+        let idx = search_from + rel;
+        let at_line_start = idx == 0 || body.as_bytes().get(idx - 1) == Some(&b'\n');
+        if at_line_start {
+            marks.push(idx);
+        }
+        search_from = idx + delimiter.len();
+    }
+    marks
+}
+
+fn split_headers_body(segment: &str) -> (HashMap<String, Vec<String>>, &str) {
+    let boundary = segment
+        .find("\r\n\r\n")
+        .map(|i| (i, 4))
+        .or_else(|| segment.find("\n\n").map(|i| (i, 2)));
+    let Some((split_at, sep_len)) = boundary else {
+        return (HashMap::new(), segment);
+    };
+
+    let mut headers: HashMap<String, Vec<String>> = HashMap::new();
+    let mut last_key: Option<String> = None;
+    for line in segment[..split_at].lines() {
+        if let Some(rest) = line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')) {
+            if let Some(key) = &last_key {
+                if let Some(values) = headers.get_mut(key) {
+                    if let Some(last) = values.last_mut() {
+                        last.push(' ');
+                        last.push_str(rest.trim());
+                    }
+                }
+            }
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim().to_string();
+            headers
+                .entry(key.clone())
+                .or_default()
+                .push(value.trim().to_string());
+            last_key = Some(key);
+        }
+    }
+
+    (headers, &segment[split_at + sep_len..])
+}
+
+fn decode_body(raw: &str, transfer_encoding: &str) -> Vec<u8> {
+    match transfer_encoding {
+        "quoted-printable" => decode_quoted_printable(raw),
+        "base64" => {
+            let cleaned: String = raw.chars().filter(|c| !c.is_whitespace()).collect();
+            STANDARD.decode(cleaned).unwrap_or_default()
+        }
+        _ => raw.as_bytes().to_vec(),
+    }
+}
+
+fn decode_quoted_printable(raw: &str) -> Vec<u8> {
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'=' {
+            if bytes[i + 1..].starts_with(b"\r\n") {
+                i += 3;
+                continue;
+            }
+            if bytes[i + 1..].starts_with(b"\n") {
+                i += 2;
+                continue;
+            }
+            if let Some(hex) = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok()) {
+                if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                    out.push(byte);
+                    i += 3;
+                    continue;
+                }
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn headers(pairs: &[(&str, &str)]) -> HashMap<String, Vec<String>> {
+        let mut headers = HashMap::new();
+        for (key, value) in pairs {
+            headers
+                .entry(key.to_string())
+                .or_insert_with(Vec::new)
+                .push(value.to_string());
+        }
+        headers
+    }
+
+    fn message_content(content_type: &str, body: &str) -> MessageContent {
+        MessageContent {
+            headers: headers(&[("Content-Type", content_type)]),
+            body: body.to_string(),
+            size: body.len(),
+            mime: None,
+        }
+    }
+
+    #[test]
+    fn decode_quoted_printable_strips_soft_breaks_and_hex_escapes() {
+        let decoded = decode_quoted_printable("Hello=3D world=\r\nmore text");
+        assert_eq!(decoded, b"Hello= worldmore text");
+    }
+
+    #[test]
+    fn split_multipart_ignores_boundary_like_text_mid_line() {
+        let body = "--B\r\nheaders\r\n\r\nprefix--Bsuffix still in part\r\n--B--\r\n";
+        let parts = split_multipart(body, "B");
+        assert_eq!(parts.len(), 1);
+        assert!(parts[0].contains("prefix--Bsuffix"));
+    }
+
+    #[test]
+    fn parse_mime_decodes_multipart_text_and_attachment() {
+        let body = "--BOUNDARY\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+Hello=3D world\r\n\
+--BOUNDARY\r\n\
+Content-Type: text/plain\r\n\
+Content-Transfer-Encoding: base64\r\n\
+Content-Disposition: attachment; filename=\"hello.txt\"\r\n\
+\r\n\
+aGVsbG8gd29ybGQ=\r\n\
+--BOUNDARY--\r\n";
+        let content = message_content("multipart/mixed; boundary=BOUNDARY", body);
+
+        let part = content.parse_mime().unwrap();
+        assert_eq!(part.content_type, "multipart/mixed");
+        assert_eq!(part.children.len(), 2);
+        assert_eq!(part.text_plain().unwrap(), "Hello= world\r\n");
+
+        let attachments = part.attachments();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].filename.as_deref(), Some("hello.txt"));
+        assert_eq!(attachments[0].mime_type, "text/plain");
+        assert_eq!(attachments[0].data, b"hello world");
+    }
+
+    #[test]
+    fn text_plain_skips_attachment_part_ordered_before_the_body() {
+        let body = "--BOUNDARY\r\n\
+Content-Type: text/plain\r\n\
+Content-Disposition: attachment; filename=\"notes.txt\"\r\n\
+\r\n\
+attachment contents\r\n\
+--BOUNDARY\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+the real body\r\n\
+--BOUNDARY--\r\n";
+        let content = message_content("multipart/mixed; boundary=BOUNDARY", body);
+
+        let part = content.parse_mime().unwrap();
+        assert_eq!(part.text_plain().unwrap(), "the real body\r\n");
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{ListMessagesParams, MailHog, Message, MessageList, SearchKind, SearchParams};
+use anyhow::Result;
+use futures::Stream;
+use std::future::Future;
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+
+impl MailHog {
+    /// Stream every captured message, driving `list_messages` pagination
+    /// internally. Pass `page_size <= 0` to use the default page size.
+    pub fn list_messages_stream(&self, page_size: i64) -> impl Stream<Item = Result<Message>> + '_ {
+        paginate(page_size, move |start, limit| {
+            self.list_messages(ListMessagesParams {
+                start: Some(start),
+                limit: Some(limit),
+            })
+        })
+    }
+
+    /// Stream every message matching `kind`/`query`, driving `search`
+    /// pagination internally. Pass `page_size <= 0` to use the default page
+    /// size.
+    pub fn search_stream(
+        &self,
+        kind: SearchKind,
+        query: String,
+        page_size: i64,
+    ) -> impl Stream<Item = Result<Message>> + '_ {
+        paginate(page_size, move |start, limit| {
+            self.search(SearchParams {
+                kind: kind.clone(),
+                query: query.clone(),
+                start: Some(start),
+                limit: Some(limit),
+            })
+        })
+    }
+}
+
+/// Drive `fetch_page(start, limit)` forward, yielding each page's items and
+/// advancing `start` until the server reports the inbox is exhausted. Shared
+/// by `list_messages_stream` and `search_stream`, which differ only in which
+/// endpoint `fetch_page` calls.
+fn paginate<F, Fut>(page_size: i64, fetch_page: F) -> impl Stream<Item = Result<Message>>
+where
+    F: Fn(i64, i64) -> Fut,
+    Fut: Future<Output = Result<MessageList>>,
+{
+    let page_size = page_size_or_default(page_size);
+    async_stream::try_stream! {
+        let mut start = 0i64;
+        loop {
+            let page = fetch_page(start, page_size).await?;
+            let count = page.count;
+            let total = page.total;
+            for item in page.items {
+                yield item;
+            }
+            start += count;
+            if count == 0 || start >= total {
+                break;
+            }
+        }
+    }
+}
+
+fn page_size_or_default(page_size: i64) -> i64 {
+    if page_size > 0 {
+        page_size
+    } else {
+        DEFAULT_PAGE_SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EmailAddr, MessageContent};
+    use chrono::Utc;
+    use futures::StreamExt;
+    use std::cell::Cell;
+    use std::collections::HashMap;
+
+    fn sample_message(id: &str) -> Message {
+        Message {
+            id: id.to_string(),
+            from: EmailAddr {
+                mailbox: "a".to_string(),
+                domain: "b.com".to_string(),
+                params: String::new(),
+                relays: None,
+            },
+            to: Vec::new(),
+            content: MessageContent {
+                headers: HashMap::new(),
+                body: String::new(),
+                size: 0,
+                mime: None,
+            },
+            created: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn paginate_advances_through_pages_until_exhausted() {
+        let pages = [
+            MessageList {
+                total: 3,
+                start: 0,
+                count: 2,
+                items: vec![sample_message("1"), sample_message("2")],
+            },
+            MessageList {
+                total: 3,
+                start: 2,
+                count: 1,
+                items: vec![sample_message("3")],
+            },
+        ];
+        let calls = Cell::new(0usize);
+
+        let stream = paginate(2, |_start, _limit| {
+            let page = pages[calls.get()].clone();
+            calls.set(calls.get() + 1);
+            async move { Ok(page) }
+        });
+
+        let items: Vec<Message> = stream.map(|m| m.unwrap()).collect().await;
+        assert_eq!(items.len(), 3);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn paginate_stops_on_empty_page() {
+        let pages = [MessageList {
+            total: 0,
+            start: 0,
+            count: 0,
+            items: Vec::new(),
+        }];
+        let calls = Cell::new(0usize);
+
+        let stream = paginate(2, |_start, _limit| {
+            let page = pages[calls.get()].clone();
+            calls.set(calls.get() + 1);
+            async move { Ok(page) }
+        });
+
+        let items: Vec<Message> = stream.map(|m| m.unwrap()).collect().await;
+        assert!(items.is_empty());
+        assert_eq!(calls.get(), 1);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::mime::header_get;
+use crate::{MailHog, Message, MessageList};
+use anyhow::Result;
+use futures::StreamExt;
+
+/// A composable filter tree evaluated client-side over fetched messages,
+/// since MailHog's native `/api/v2/search` only matches one dimension at a
+/// time.
+#[derive(Debug, Clone)]
+pub enum MessageQuery {
+    From(String),
+    To(String),
+    Containing(String),
+    And(Box<MessageQuery>, Box<MessageQuery>),
+    Or(Box<MessageQuery>, Box<MessageQuery>),
+    Not(Box<MessageQuery>),
+}
+
+impl MessageQuery {
+    pub fn from(value: impl Into<String>) -> Self {
+        MessageQuery::From(value.into())
+    }
+
+    pub fn to(value: impl Into<String>) -> Self {
+        MessageQuery::To(value.into())
+    }
+
+    pub fn containing(value: impl Into<String>) -> Self {
+        MessageQuery::Containing(value.into())
+    }
+
+    pub fn and(self, other: MessageQuery) -> Self {
+        MessageQuery::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: MessageQuery) -> Self {
+        MessageQuery::Or(Box::new(self), Box::new(other))
+    }
+
+    pub fn negate(self) -> Self {
+        MessageQuery::Not(Box::new(self))
+    }
+
+    fn matches(&self, message: &Message) -> bool {
+        match self {
+            MessageQuery::From(value) => contains_ignore_case(&message.from.to_string(), value),
+            MessageQuery::To(value) => message
+                .to
+                .iter()
+                .any(|addr| contains_ignore_case(&addr.to_string(), value)),
+            MessageQuery::Containing(value) => {
+                let subject = header_get(&message.content.headers, "Subject").unwrap_or_default();
+                contains_ignore_case(subject, value) || contains_ignore_case(&message.content.body, value)
+            }
+            MessageQuery::And(a, b) => a.matches(message) && b.matches(message),
+            MessageQuery::Or(a, b) => a.matches(message) || b.matches(message),
+            MessageQuery::Not(inner) => !inner.matches(message),
+        }
+    }
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_ascii_lowercase().contains(&needle.to_ascii_lowercase())
+}
+
+impl MailHog {
+    /// Evaluate `q` against every captured message, streaming candidates
+    /// page by page rather than loading the whole inbox up front.
+    pub async fn query(&self, q: MessageQuery) -> Result<MessageList> {
+        let mut items = Vec::new();
+        let mut stream = Box::pin(self.list_messages_stream(0));
+        while let Some(message) = stream.next().await {
+            let message = message?;
+            if q.matches(&message) {
+                items.push(message);
+            }
+        }
+
+        let count = items.len() as i64;
+        Ok(MessageList {
+            total: count,
+            start: 0,
+            count,
+            items,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EmailAddr, MessageContent};
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn addr(mailbox: &str, domain: &str) -> EmailAddr {
+        EmailAddr {
+            mailbox: mailbox.to_string(),
+            domain: domain.to_string(),
+            params: String::new(),
+            relays: None,
+        }
+    }
+
+    fn sample_message(from: &str, to: &str, subject: &str, body: &str) -> Message {
+        let (from_mailbox, from_domain) = from.split_once('@').unwrap();
+        let (to_mailbox, to_domain) = to.split_once('@').unwrap();
+        let mut headers = HashMap::new();
+        headers.insert("Subject".to_string(), vec![subject.to_string()]);
+
+        Message {
+            id: "1".to_string(),
+            from: addr(from_mailbox, from_domain),
+            to: vec![addr(to_mailbox, to_domain)],
+            content: MessageContent {
+                headers,
+                body: body.to_string(),
+                size: body.len(),
+                mime: None,
+            },
+            created: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn matches_from_to_and_containing_case_insensitively() {
+        let message = sample_message("Alice@Example.com", "bob@example.com", "URGENT request", "please help");
+
+        assert!(MessageQuery::from("alice@example.com").matches(&message));
+        assert!(MessageQuery::to("BOB@EXAMPLE.COM").matches(&message));
+        assert!(MessageQuery::containing("urgent").matches(&message));
+        assert!(MessageQuery::containing("help").matches(&message));
+        assert!(!MessageQuery::from("carol@example.com").matches(&message));
+    }
+
+    #[test]
+    fn and_or_not_combine_as_expected() {
+        let message = sample_message("alice@example.com", "bob@example.com", "hello", "body text");
+
+        assert!(MessageQuery::from("alice")
+            .and(MessageQuery::containing("hello"))
+            .matches(&message));
+        assert!(!MessageQuery::from("alice")
+            .and(MessageQuery::containing("nope"))
+            .matches(&message));
+
+        assert!(MessageQuery::from("nobody")
+            .or(MessageQuery::containing("hello"))
+            .matches(&message));
+
+        assert!(MessageQuery::from("alice")
+            .and(MessageQuery::to("carol").negate())
+            .matches(&message));
+        assert!(!MessageQuery::from("alice").negate().matches(&message));
+    }
+}
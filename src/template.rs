@@ -0,0 +1,262 @@
+use crate::mime::header_get;
+use crate::{Attachment, Message};
+use std::collections::HashMap;
+
+/// A draft assembled from a captured [`Message`], ready to hand to an SMTP
+/// transport such as lettre.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MessageTemplate {
+    pub headers: HashMap<String, String>,
+    pub body: String,
+    pub attachments: Vec<Attachment>,
+}
+
+impl Message {
+    /// Build a reply draft: `From`/`To` swapped, `Subject` prefixed with
+    /// `Re: `, `In-Reply-To`/`References` set from the original `Message-ID`,
+    /// and the decoded text body quoted with `> `. When `reply_all` is set,
+    /// the other original recipients are carried over in `To`.
+    pub fn reply_template(&self, reply_all: bool) -> MessageTemplate {
+        let from = self
+            .to
+            .first()
+            .map(|addr| addr.to_string())
+            .unwrap_or_default();
+        let mut to = vec![self.from.to_string()];
+        if reply_all {
+            to.extend(self.to.iter().skip(1).map(|addr| addr.to_string()));
+        }
+
+        let mut headers = HashMap::new();
+        headers.insert("From".to_string(), from);
+        headers.insert("To".to_string(), to.join(", "));
+        headers.insert("Subject".to_string(), prefixed(&self.header("Subject"), "Re: "));
+        if let Some(message_id) = self.header_opt("Message-ID") {
+            headers.insert("In-Reply-To".to_string(), message_id.clone());
+            headers.insert("References".to_string(), message_id);
+        }
+
+        MessageTemplate {
+            headers,
+            body: quote_lines(&self.decoded_text()),
+            attachments: Vec::new(),
+        }
+    }
+
+    /// Build a forward draft: `Subject` prefixed with `Fwd: `, a banner with
+    /// the original `From`/`To`/`Date`/`Subject`, and the original
+    /// attachments carried through.
+    pub fn forward_template(&self) -> MessageTemplate {
+        let subject = self.header("Subject");
+
+        let mut headers = HashMap::new();
+        headers.insert("Subject".to_string(), prefixed(&subject, "Fwd: "));
+
+        let to = self
+            .to
+            .iter()
+            .map(|addr| addr.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let banner = format!(
+            "---------- Forwarded message ----------\nFrom: {}\nTo: {}\nDate: {}\nSubject: {}\n\n",
+            self.from,
+            to,
+            self.created.to_rfc2822(),
+            subject,
+        );
+
+        let attachments = self
+            .content
+            .parse_mime()
+            .map(|part| part.attachments())
+            .unwrap_or_default();
+
+        MessageTemplate {
+            headers,
+            body: format!("{}{}", banner, self.decoded_text()),
+            attachments,
+        }
+    }
+
+    fn header(&self, name: &str) -> String {
+        self.header_opt(name).unwrap_or_default()
+    }
+
+    fn header_opt(&self, name: &str) -> Option<String> {
+        header_get(&self.content.headers, name).map(|s| s.to_string())
+    }
+
+    fn decoded_text(&self) -> String {
+        let Ok(part) = self.content.parse_mime() else {
+            return self.content.body.clone();
+        };
+        if let Some(text) = part.text_plain().or_else(|| part.text_html()) {
+            return text;
+        }
+        if part.children.is_empty() {
+            String::from_utf8_lossy(&part.body).into_owned()
+        } else {
+            String::new()
+        }
+    }
+}
+
+fn prefixed(subject: &str, prefix: &str) -> String {
+    if subject.to_ascii_lowercase().starts_with(&prefix.to_ascii_lowercase()) {
+        subject.to_string()
+    } else {
+        format!("{}{}", prefix, subject)
+    }
+}
+
+fn quote_lines(body: &str) -> String {
+    body.lines()
+        .map(|line| format!("> {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EmailAddr;
+    use chrono::Utc;
+
+    fn addr(mailbox: &str, domain: &str) -> EmailAddr {
+        EmailAddr {
+            mailbox: mailbox.to_string(),
+            domain: domain.to_string(),
+            params: String::new(),
+            relays: None,
+        }
+    }
+
+    fn sample_message(subject: &str, body: &str) -> Message {
+        let mut headers = HashMap::new();
+        headers.insert("Subject".to_string(), vec![subject.to_string()]);
+        headers.insert("Message-ID".to_string(), vec!["<abc@mailhog>".to_string()]);
+
+        Message {
+            id: "1".to_string(),
+            from: addr("sender", "example.com"),
+            to: vec![addr("me", "example.com"), addr("other", "example.com")],
+            content: crate::MessageContent {
+                headers,
+                body: body.to_string(),
+                size: body.len(),
+                mime: None,
+            },
+            created: Utc::now(),
+        }
+    }
+
+    fn sample_message_with_headers(extra_headers: &[(&str, &str)], body: &str) -> Message {
+        let mut headers = HashMap::new();
+        headers.insert("Subject".to_string(), vec!["Hello".to_string()]);
+        for (key, value) in extra_headers {
+            headers.insert(key.to_string(), vec![value.to_string()]);
+        }
+
+        Message {
+            id: "1".to_string(),
+            from: addr("sender", "example.com"),
+            to: vec![addr("me", "example.com")],
+            content: crate::MessageContent {
+                headers,
+                body: body.to_string(),
+                size: body.len(),
+                mime: None,
+            },
+            created: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn reply_template_swaps_from_to_and_prefixes_subject() {
+        let message = sample_message("Hello", "line one\nline two");
+        let template = message.reply_template(false);
+
+        assert_eq!(template.headers["From"], "me@example.com");
+        assert_eq!(template.headers["To"], "sender@example.com");
+        assert_eq!(template.headers["Subject"], "Re: Hello");
+        assert_eq!(template.headers["In-Reply-To"], "<abc@mailhog>");
+        assert_eq!(template.headers["References"], "<abc@mailhog>");
+        assert_eq!(template.body, "> line one\n> line two");
+    }
+
+    #[test]
+    fn reply_template_does_not_double_prefix_subject() {
+        let message = sample_message("Re: Hello", "body");
+        let template = message.reply_template(false);
+        assert_eq!(template.headers["Subject"], "Re: Hello");
+    }
+
+    #[test]
+    fn reply_all_carries_over_remaining_recipients() {
+        let message = sample_message("Hello", "body");
+        let template = message.reply_template(true);
+        assert_eq!(template.headers["To"], "sender@example.com, other@example.com");
+    }
+
+    #[test]
+    fn forward_template_prefixes_subject_and_adds_banner() {
+        let message = sample_message("Hello", "original body");
+        let template = message.forward_template();
+
+        assert_eq!(template.headers["Subject"], "Fwd: Hello");
+        assert!(template.body.starts_with("---------- Forwarded message ----------\n"));
+        assert!(template.body.contains("From: sender@example.com"));
+        assert!(template.body.contains("To: me@example.com, other@example.com"));
+        assert!(template.body.contains("Subject: Hello"));
+        assert!(template.body.ends_with("original body"));
+    }
+
+    #[test]
+    fn reply_falls_back_to_html_body_for_html_only_multipart_message() {
+        let body = "--BOUNDARY\r\n\
+Content-Type: text/html\r\n\
+\r\n\
+<p>hi</p>\r\n\
+--BOUNDARY--\r\n";
+        let message = sample_message_with_headers(
+            &[("Content-Type", "multipart/alternative; boundary=BOUNDARY")],
+            body,
+        );
+
+        let template = message.reply_template(false);
+        assert_eq!(template.body, "> <p>hi</p>");
+    }
+
+    #[test]
+    fn reply_decodes_quoted_printable_html_only_body() {
+        let message = sample_message_with_headers(
+            &[
+                ("Content-Type", "text/html"),
+                ("Content-Transfer-Encoding", "quoted-printable"),
+            ],
+            "<p>hi=3D there</p>",
+        );
+
+        let template = message.reply_template(false);
+        assert_eq!(template.body, "> <p>hi= there</p>");
+    }
+
+    #[test]
+    fn reply_never_surfaces_raw_multipart_when_no_text_part_matches() {
+        let body = "--BOUNDARY\r\n\
+Content-Type: image/png\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+aGVsbG8=\r\n\
+--BOUNDARY--\r\n";
+        let message = sample_message_with_headers(
+            &[("Content-Type", "multipart/mixed; boundary=BOUNDARY")],
+            body,
+        );
+
+        let template = message.reply_template(false);
+        assert!(!template.body.contains("BOUNDARY"));
+        assert!(!template.body.contains("Content-Type"));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{MailHog, Message};
+use anyhow::Result;
+use futures::{Stream, StreamExt};
+use std::time::Duration;
+use tokio::time::sleep;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+impl MailHog {
+    /// Stream each [`Message`] as MailHog receives it, over the `/api/v2/websocket`
+    /// endpoint. Connection drops are retried with exponential backoff, capped at
+    /// `MAX_BACKOFF`, so a persistently unreachable endpoint doesn't busy-loop the
+    /// consumer; the backoff resets once a text frame is read successfully.
+    pub fn watch(&self) -> impl Stream<Item = Result<Message>> {
+        let ws_url = self.websocket_url();
+        async_stream::stream! {
+            let mut backoff = INITIAL_BACKOFF;
+            loop {
+                match connect_async(&ws_url).await {
+                    Ok((mut ws, _)) => {
+                        while let Some(frame) = ws.next().await {
+                            match frame {
+                                Ok(WsMessage::Text(text)) => {
+                                    backoff = INITIAL_BACKOFF;
+                                    yield serde_json::from_str::<Message>(&text).map_err(Into::into);
+                                }
+                                Ok(_) => continue,
+                                Err(e) => {
+                                    yield Err(e.into());
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => yield Err(e.into()),
+                }
+                sleep(backoff).await;
+                backoff = next_backoff(backoff);
+            }
+        }
+    }
+
+    fn websocket_url(&self) -> String {
+        format!(
+            "{}/api/v2/websocket",
+            self.base_url.replacen("http", "ws", 1)
+        )
+    }
+}
+
+fn next_backoff(current: Duration) -> Duration {
+    (current * 2).min(MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_backoff_doubles_until_capped() {
+        let mut backoff = INITIAL_BACKOFF;
+        assert_eq!(backoff, Duration::from_secs(1));
+
+        backoff = next_backoff(backoff);
+        assert_eq!(backoff, Duration::from_secs(2));
+
+        backoff = next_backoff(backoff);
+        assert_eq!(backoff, Duration::from_secs(4));
+
+        for _ in 0..10 {
+            backoff = next_backoff(backoff);
+        }
+        assert_eq!(backoff, MAX_BACKOFF);
+    }
+}